@@ -16,6 +16,8 @@ use std::path::PathBuf;
 use anyhow::{anyhow, Result};
 use clap::{Parser, ValueEnum};
 
+use exifgeo::reverse_geocoder::Provider;
+
 ///
 /// ログレベルを指し示す列挙子
 ///
@@ -62,6 +64,53 @@ impl ToString for LogLevel {
     }
 }
 
+///
+/// コマンドラインでの指定用のプロバイダ列挙子
+///
+/// # 注記
+/// `exifgeo::reverse_geocoder::Provider`はライブラリ側の型で`clap`に依存さ
+/// せたくない為、CLI引数解析用に本列挙子を用意し、`Options::provider()`で
+/// ライブラリ側の型に変換する。
+///
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+#[clap(rename_all = "lower")]
+enum ProviderArg {
+    /// 国土地理院の逆ジオコーディングAPI(日本国内限定)
+    Gsi,
+
+    /// Nominatim(OpenStreetMap)の逆ジオコーディングAPI(世界対応)
+    Nominatim,
+}
+
+// Provider(ライブラリ側の型)への変換
+impl From<ProviderArg> for Provider {
+    fn from(value: ProviderArg) -> Self {
+        match value {
+            ProviderArg::Gsi => Provider::Gsi,
+            ProviderArg::Nominatim => Provider::Nominatim,
+        }
+    }
+}
+
+///
+/// 結果の出力形式を指し示す列挙子
+///
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+#[clap(rename_all = "lower")]
+pub(crate) enum OutputFormat {
+    /// 人間向けのテキスト形式(従来通りの表示)
+    Text,
+
+    /// JSON形式
+    Json,
+
+    /// CSV形式
+    Csv,
+
+    /// GeoJSON形式
+    Geojson,
+}
+
 ///
 /// コマンドラインオプションをまとめた構造体
 ///
@@ -85,6 +134,29 @@ pub(crate) struct Options {
         default_value_t = default_municd_cache())]
     municd_cache: String,
 
+    /// 位置情報の量子化精度(小数点以下の桁数)の指定
+    #[arg(short = 'p', long = "precision", value_name = "N",
+        default_value_t = 4)]
+    precision: u32,
+
+    /// 使用する逆ジオコーディングAPIのプロバイダの指定
+    #[arg(long = "provider", value_name = "PROVIDER",
+        default_value = "gsi", ignore_case = true)]
+    provider: ProviderArg,
+
+    /// 並行処理するワーカースレッド数の指定
+    #[arg(short = 'j', long = "jobs", value_name = "N", default_value_t = 1)]
+    jobs: usize,
+
+    /// プロバイダへの秒間問い合わせ数の上限の指定(0は無制限)
+    #[arg(long = "rate", value_name = "REQ_PER_SEC", default_value_t = 0.0)]
+    rate: f64,
+
+    /// 結果の出力形式の指定
+    #[arg(short = 'o', long = "output", value_name = "FORMAT",
+        default_value = "text", ignore_case = true)]
+    output: OutputFormat,
+
     /// 処理対象のファイル名
     #[arg()]
     target_files: Vec<PathBuf>,
@@ -121,6 +193,56 @@ impl Options {
         &self.target_files
     }
 
+    ///
+    /// 位置情報の量子化精度へのアクセサ
+    ///
+    /// # 戻り値
+    /// 位置情報の量子化に用いる精度(小数点以下の桁数)
+    ///
+    pub(crate) fn precision(&self) -> u32 {
+        self.precision
+    }
+
+    ///
+    /// 逆ジオコーディングAPIのプロバイダへのアクセサ
+    ///
+    /// # 戻り値
+    /// 使用する逆ジオコーディングAPIのプロバイダ
+    ///
+    pub(crate) fn provider(&self) -> Provider {
+        self.provider.into()
+    }
+
+    ///
+    /// 並行処理するワーカースレッド数へのアクセサ
+    ///
+    /// # 戻り値
+    /// 並行処理するワーカースレッド数
+    ///
+    pub(crate) fn jobs(&self) -> usize {
+        self.jobs
+    }
+
+    ///
+    /// 秒間問い合わせ数の上限へのアクセサ
+    ///
+    /// # 戻り値
+    /// プロバイダへの秒間問い合わせ数の上限(`0.0`は無制限)
+    ///
+    pub(crate) fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    ///
+    /// 結果の出力形式へのアクセサ
+    ///
+    /// # 戻り値
+    /// 結果の出力形式
+    ///
+    pub(crate) fn output(&self) -> OutputFormat {
+        self.output
+    }
+
     ///
     /// 設定情報のバリデーション
     ///
@@ -134,6 +256,11 @@ impl Options {
             return Err(anyhow!("target files is not specified"));
         }
 
+        // ワーカースレッド数のチェック
+        if self.jobs == 0 {
+            return Err(anyhow!("jobs must be greater than zero"));
+        }
+
         Ok(())
     }
 }
@@ -14,21 +14,49 @@ use std::path::Path;
 
 use anyhow::{anyhow, Result};
 use exif::{Exif, Tag};
+use thiserror::Error;
+
+use crate::position::Position;
+
+///
+/// GPS座標の妥当性検証で検出されるエラー
+///
+#[derive(Debug, Error)]
+pub(crate) enum GeoError {
+    /// 緯度が`[-90, 90]`の範囲外である事を示すエラー
+    #[error("invalid latitude: {0}")]
+    BadLatitude(f64),
+
+    /// 経度が`[-180, 180]`の範囲外である事を示すエラー
+    #[error("invalid longitude: {0}")]
+    BadLongitude(f64),
+
+    /// 緯度または経度が有限な数値ではない事を示すエラー
+    #[error("non-finite coordinate")]
+    NonFiniteCoordinate,
+
+    /// 緯度または経度の有理数表現が度・分・秒の3要素を持たない事を示すエラー
+    #[error("malformed GPS rational value (expected 3 components, got {0})")]
+    MalformedCoordinate(usize),
+}
 
 ///
 /// ExifのGPS情報の読み出し
 ///
 /// # 引数
 /// * `path` - 読み出し対象のファイルのパス
+/// * `precision` - 位置情報の量子化に用いる精度(小数点以下の桁数)
 ///
 /// # 戻り値
-/// GPS 情報の読み出しに成功した場合は読み出した緯度と経度をパックしたタプルを
+/// GPS 情報の読み出しに成功した場合は読み出した緯度経度を`Position`にパックし
 /// `Ok(Some())` でラップして返す。対象ファイルにGPS情報が存在しなかった場合は
 /// `Ok(None)`を返す。
-/// 処理に失敗した場合(対象ファイルが未サポートの形式の場合も含む)はエラー情報 
+/// 処理に失敗した場合(対象ファイルが未サポートの形式の場合も含む)はエラー情報
 /// を`Err()`でラップして返す。
 ///
-pub(crate) fn read(path: impl AsRef<Path>) -> Result<Option<(f64, f64)>> {
+pub fn read(
+    path: impl AsRef<Path>, precision: u32
+) -> Result<Option<Position>> {
     /*
      * Exif情報の読み出し
      */
@@ -71,7 +99,43 @@ pub(crate) fn read(path: impl AsRef<Path>) -> Result<Option<(f64, f64)>> {
     /*
      * 緯度及び経度を有理数表現から浮動小数点数に変換し、戻り値として返却
      */
-    Ok(Some((conv_degree(&lat, &lat_ref)?, conv_degree(&lng, &lng_ref)?)))
+    let lat = conv_degree(&lat, &lat_ref)?;
+    let lng = conv_degree(&lng, &lng_ref)?;
+
+    /*
+     * 変換した緯度経度の妥当性を検証
+     */
+    validate_coordinate(lat, lng)?;
+
+    Ok(Some(Position::new(lat, lng, precision)))
+}
+
+///
+/// 緯度経度の妥当性の検証
+///
+/// # 引数
+/// * `lat` - 検証対象の北緯
+/// * `lng` - 検証対象の東経
+///
+/// # 戻り値
+/// 検証に成功した場合は`Ok(())`を返す。`lat`が`[-90, 90]`の範囲外、`lng`が
+/// `[-180, 180]`の範囲外、または一方でも有限な数値でない場合は`GeoError`を
+/// `Err()`でラップして返す。
+///
+fn validate_coordinate(lat: f64, lng: f64) -> Result<(), GeoError> {
+    if !lat.is_finite() || !lng.is_finite() {
+        return Err(GeoError::NonFiniteCoordinate);
+    }
+
+    if lat < -90.0 || lat > 90.0 {
+        return Err(GeoError::BadLatitude(lat));
+    }
+
+    if lng < -180.0 || lng > 180.0 {
+        return Err(GeoError::BadLongitude(lng));
+    }
+
+    Ok(())
 }
 
 ///
@@ -109,6 +173,10 @@ fn conv_degree(value: &exif::Field, reference: &exif::Field) -> Result<f64> {
     let reference = reference.display_value().to_string();
 
     if let exif::Value::Rational(ref fractions) = value.value {
+        if fractions.len() < 3 {
+            return Err(GeoError::MalformedCoordinate(fractions.len()).into());
+        }
+
         let deg = fractions[0].to_f64();
         let min = fractions[1].to_f64();
         let sec = fractions[2].to_f64();
@@ -0,0 +1,19 @@
+/*
+ * Reverse geocoder for Exif location data
+ *
+ *  Copyright (C) 2025 Hiroshi KUWAGATA <kgt9221@gmail.com>
+ */
+
+//!
+//! Exifの位置情報からの逆ジオコーディングを行う為のライブラリ
+//!
+//! コマンドライン引数の解析やプロセスの並行実行といったCLI固有の処理は含ま
+//! ず、`gps_info::read`によるExifからの位置情報取得、及び`reverse_geocoder`
+//! による緯度経度から住所への変換のみを提供する。
+//!
+
+pub mod gps_info;
+pub mod municd;
+pub mod position;
+pub mod rate_limiter;
+pub mod reverse_geocoder;
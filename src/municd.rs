@@ -10,9 +10,8 @@
 //!
 
 use std::collections::HashMap;
-use std::fs::File;
 use std::path::Path;
-use std::time::{SystemTime, Duration};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use anyhow::{anyhow, Result};
 use regex::Regex;
@@ -66,7 +65,7 @@ impl MuniCdRecord {
     /// 市町村コードへのアクセサ
     ///
     #[allow(dead_code)]
-    pub(crate) fn code(&self) -> usize {
+    pub fn code(&self) -> usize {
         self.code
     }
 
@@ -74,7 +73,7 @@ impl MuniCdRecord {
     /// 都道府県名へのアクセサ
     ///
     #[allow(dead_code)]
-    pub(crate) fn pref_name(&self) -> String {
+    pub fn pref_name(&self) -> String {
         self.pref.clone()
     }
 
@@ -82,7 +81,7 @@ impl MuniCdRecord {
     /// 市町村名へのアクセサ
     ///
     #[allow(dead_code)]
-    pub(crate) fn town_name(&self) -> String {
+    pub fn town_name(&self) -> String {
         self.town.clone()
     }
 }
@@ -94,6 +93,23 @@ impl ToString for MuniCdRecord {
     }
 }
 
+///
+/// キャッシュファイルのフォーマット
+///
+/// # 注記
+/// 有効期限の判定をファイルシステムのmtime(バックアップやコピーで容易に失
+/// われる)ではなく、ダウンロードに成功した時刻そのもので行う為、取得時刻を
+/// ファイル内容に埋め込む。
+///
+#[derive(Debug, Deserialize, Serialize)]
+struct CacheFile {
+    /// ダウンロードに成功した時刻(UNIXエポックからの経過秒数)
+    fetched_at: u64,
+
+    /// 市町村コードをキーとしたMuniCDレコードのハッシュマップ
+    records: HashMap<String, MuniCdRecord>,
+}
+
 ///
 /// 市町村データの読み込み
 ///
@@ -105,111 +121,97 @@ impl ToString for MuniCdRecord {
 /// オブジェクトを`Ok()`でラップして返す。
 ///
 /// # 注記
-/// まず、キャッシュファイルからの読み込みを試みて失敗 (もしくはキャッシュファ
-/// イルが)無効な場合、サーバからダウンロードしキャッシュを更新する。
+/// まず、キャッシュファイルからの読み込みを試みる。キャッシュファイルが存在
+/// しない、もしくは内容が破損している場合はサーバからダウンロードする。キャ
+/// ッシュファイルの読み込みに成功したものの有効期限が切れている場合は再ダウ
+/// ンロードを試みるが、それが失敗した場合(サーバが到達不能等)は警告を出し
+/// た上で期限切れのキャッシュをそのまま使用する。
 ///
-pub(crate) fn load(cache_path: impl AsRef<Path>)
+pub fn load(cache_path: impl AsRef<Path>)
     -> Result<HashMap<String, MuniCdRecord>>
 {
-    match load_from_cache(cache_path.as_ref()) {
-        Ok(map) => {
-            info!("read muniCd data from cache file.");
-            Ok(map)
-        }
+    let cache_path = cache_path.as_ref();
+
+    let cache = match read_cache_file(cache_path) {
+        Ok(cache) => cache,
 
         Err(err) => {
             error!("{}", err);
-            download(cache_path)
+            return download(cache_path);
+        }
+    };
+
+    if !is_expired(&cache) {
+        info!("read muniCd data from cache file.");
+        return Ok(cache.records);
+    }
+
+    info!("cached muniCd data is expired, try to refresh.");
+    match download(cache_path) {
+        Ok(map) => Ok(map),
+
+        Err(err) => {
+            warn!("refresh muniCd data failed, use stale cache: {}", err);
+            Ok(cache.records)
         }
     }
 }
 
 ///
-/// キャッシュファイルからの市町村データの読み込み
+/// キャッシュファイルの読み込み
 ///
 /// # 引数
 /// * `cache_path` - キャッシュファイルへのパス
 ///
 /// # 戻り値
-/// 処理に成功した場合、市町村コードをキーとしたMuniCDレコードのハッシュマップ
-/// オブジェクトを`Ok()`でラップして返す。
+/// 処理に成功した場合、キャッシュファイルの内容を`Ok()`でラップして返す。
 ///
-fn load_from_cache(cache_path: &Path)
-    -> Result<HashMap<String, MuniCdRecord>>
-{
-    /*
-     * キャッシュファイルの有効性の確認
-     */
-    if !is_available(cache_path) {
-        return Err(anyhow!("cache file is expired."));
-    }
-
-    /*
-     * キャッシュファイルのオープン
-     */
-    let file = match File::open(cache_path) {
-        Ok(file) => file,
+/// # 注記
+/// `fetched_at`を持たない旧形式(市町村コードをキーとしたレコードのハッシュ
+/// マップそのもの)のキャッシュファイルも読み込めるようにする。旧形式の場合
+/// は取得時刻が不明な為`fetched_at`を`0`とみなし、即座に有効期限切れ扱いと
+/// するが、これにより`load()`側の「再ダウンロード失敗時は期限切れキャッシ
+/// ュをそのまま使う」フォールバックが働き、旧形式キャッシュしか無い環境でも
+/// サーバ到達不能時に失敗しない。
+///
+fn read_cache_file(cache_path: &Path) -> Result<CacheFile> {
+    let content = match std::fs::read_to_string(cache_path) {
+        Ok(content) => content,
         Err(err) => return Err(anyhow!("open cache file failed: {}", err)),
     };
 
-    /*
-     * キャッシュファイルの読み込み
-     */
-    match serde_json::from_reader(file) {
-        Ok(map) => Ok(map),
-        Err(err) => return Err(anyhow!("parse JSON failed: {}", err)),
+    if let Ok(cache) = serde_json::from_str::<CacheFile>(&content) {
+        return Ok(cache);
+    }
+
+    match serde_json::from_str::<HashMap<String, MuniCdRecord>>(&content) {
+        Ok(records) => {
+            info!("detected legacy-format muniCd cache file.");
+            Ok(CacheFile { fetched_at: 0, records })
+        }
+
+        Err(err) => Err(anyhow!("parse JSON failed: {}", err)),
     }
 }
 
 ///
-/// キャッシュファイルの有効性の確認
+/// キャッシュファイルの有効期限切れ判定
 ///
 /// # 引数
-/// * `path` - 確認対象のファイルのパス
+/// * `cache` - 判定対象のキャッシュファイルの内容
 ///
 /// # 戻り値
-/// ファイルが有効な場合は真を返す
+/// `fetched_at`からの経過時間が規定の日数を過ぎている場合は真を返す。
 ///
-fn is_available(path: &Path) -> bool {
-    /*
-     * ファイルが存在するか否かを確認
-     */
-    if !path.exists() {
-        error!("cache file is not exists.");
-        return false;
-    }
+fn is_expired(cache: &CacheFile) -> bool {
+    let fetched_at = UNIX_EPOCH + Duration::from_secs(cache.fetched_at);
 
-    /*
-     * ファイルの更新から規定の期間を過ぎているか否かの確認
-     */
-
-    // ファイルのメタ情報の取得
-    let meta = match path.metadata() {
-        Ok(meta) => meta,
-        Err(err) => {
-            error!("metadata read failed: {}", err);
-            return false;
-        }
-    };
-
-    // ファイル更新時刻の取得
-    let mtime = match meta.modified() {
-        Ok(mtime) => mtime,
-        Err(err) => {
-            error!("read modified time failed: {}", err);
-            return false;
-        }
-    };
-
-    // 直近の更新からの経過時間評価(規定の日数を過ぎてたら無効)
-    match SystemTime::now().duration_since(mtime) {
-        Ok(diff) => {
-            diff < Duration::from_secs(86400 * EXPIRE_DAYS)
-        }
+    match SystemTime::now().duration_since(fetched_at) {
+        Ok(diff) => diff >= Duration::from_secs(86400 * EXPIRE_DAYS),
 
         Err(err) => {
             error!("calc since duration failed: {}", err);
-            return false;
+            false
         }
     }
 }
@@ -224,12 +226,13 @@ fn is_available(path: &Path) -> bool {
 /// 市町村コードをキーとしたMuniCDレコードのハッシュマップオブジェクトを返す。
 ///
 /// # 注記
-/// データベースのダウンロードと同時に、キャッシュファイルへの書き込みを行う。
+/// データベースのダウンロードと同時に、取得時刻を添えてキャッシュファイルへ
+/// の書き込みを行う。
 ///
 fn download(cache_path: impl AsRef<Path>)
     -> Result<HashMap<String, MuniCdRecord>>
 {
-    let mut ret = HashMap::new();
+    let mut records = HashMap::new();
     let re = Regex::new(RECORD_RE)?;
 
     info!("try download muniCd data.");
@@ -239,13 +242,16 @@ fn download(cache_path: impl AsRef<Path>)
             let code = captures[2].parse::<usize>()?;
             let town = captures[3].to_string();
 
-            ret.insert(
+            records.insert(
                 format!("{:06}", code),
                 MuniCdRecord::new(code, pref, town)
             );
         }
     }
 
+    let fetched_at = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let cache = CacheFile { fetched_at, records };
+
     info!("save muniCd data to cache file.");
     if let Some(dir) = cache_path.as_ref().parent() {
         if !dir.exists() {
@@ -253,7 +259,7 @@ fn download(cache_path: impl AsRef<Path>)
         }
     }
 
-    std::fs::write(cache_path, serde_json::to_string(&ret)?)?;
+    std::fs::write(cache_path, serde_json::to_string(&cache)?)?;
 
-    Ok(ret)
+    Ok(cache.records)
 }
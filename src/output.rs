@@ -0,0 +1,208 @@
+/*
+ * Reverse geocoder for Exif location data
+ *
+ *  Copyright (C) 2025 Hiroshi KUWAGATA <kgt9221@gmail.com>
+ */
+
+//!
+//! 処理結果をJSON/CSV/GeoJSON形式に整形して出力する処理をまとめたモジュール
+//!
+
+use anyhow::Result;
+use exifgeo::position::Position;
+use serde::Serialize;
+
+use crate::cmd_args::OutputFormat;
+
+///
+/// 1ファイル分の処理結果
+///
+/// # 注記
+/// `worker`モジュールから渡される処理結果を出力形式に依存しない形で保持す
+/// る。位置情報を取得できなかった場合及びエラーが発生した場合は`position`/
+/// `address`が`None`となる。
+///
+pub(crate) struct Record {
+    /// 処理対象のファイル名
+    pub(crate) file: String,
+
+    /// 取得できた位置情報(取得できなかった場合は`None`)
+    pub(crate) position: Option<Position>,
+
+    /// 取得できた住所(取得できなかった場合は`None`)
+    pub(crate) address: Option<String>,
+
+    /// 処理中に発生したエラーの内容(無い場合は`None`)
+    pub(crate) error: Option<String>,
+}
+
+///
+/// JSON出力用のレコード
+///
+#[derive(Serialize)]
+struct JsonRecord<'a> {
+    file: &'a str,
+    lat: Option<f64>,
+    lng: Option<f64>,
+    address: Option<&'a str>,
+    error: Option<&'a str>,
+}
+
+///
+/// GeoJSONの`FeatureCollection`
+///
+#[derive(Serialize)]
+struct FeatureCollection<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    features: Vec<Feature<'a>>,
+}
+
+///
+/// GeoJSONの`Feature`
+///
+#[derive(Serialize)]
+struct Feature<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    geometry: Option<Geometry>,
+    properties: Properties<'a>,
+}
+
+///
+/// GeoJSONの`Point`ジオメトリ
+///
+#[derive(Serialize)]
+struct Geometry {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    coordinates: [f64; 2],
+}
+
+///
+/// GeoJSONのFeatureに付随するプロパティ
+///
+#[derive(Serialize)]
+struct Properties<'a> {
+    file: &'a str,
+    address: Option<&'a str>,
+    error: Option<&'a str>,
+}
+
+///
+/// 処理結果の出力
+///
+/// # 引数
+/// * `records` - 入力順に整列された処理結果
+/// * `format` - 出力形式
+///
+/// # 戻り値
+/// 出力に成功した場合は`Ok(())`を返す。
+///
+/// # 注記
+/// `OutputFormat::Text`は`worker`モジュールが処理の完了と同時に逐次出力して
+/// いる為、本関数では何も行わない。
+///
+pub(crate) fn emit(records: &[Record], format: OutputFormat) -> Result<()> {
+    match format {
+        OutputFormat::Text => Ok(()),
+        OutputFormat::Json => emit_json(records),
+        OutputFormat::Csv => emit_csv(records),
+        OutputFormat::Geojson => emit_geojson(records),
+    }
+}
+
+///
+/// JSON形式での出力
+///
+fn emit_json(records: &[Record]) -> Result<()> {
+    let json_records: Vec<_> = records.iter().map(to_json_record).collect();
+
+    println!("{}", serde_json::to_string_pretty(&json_records)?);
+
+    Ok(())
+}
+
+///
+/// レコードのJSON出力用表現への変換
+///
+fn to_json_record(record: &Record) -> JsonRecord {
+    JsonRecord {
+        file: &record.file,
+        lat: record.position.map(|pos| pos.lat()),
+        lng: record.position.map(|pos| pos.lng()),
+        address: record.address.as_deref(),
+        error: record.error.as_deref(),
+    }
+}
+
+///
+/// CSV形式での出力
+///
+fn emit_csv(records: &[Record]) -> Result<()> {
+    println!("filename,lat,lng,address,error");
+
+    for record in records {
+        let lat = record.position.map_or(String::new(), |pos| pos.lat().to_string());
+        let lng = record.position.map_or(String::new(), |pos| pos.lng().to_string());
+        let address = record.address.as_deref().unwrap_or("");
+        let error = record.error.as_deref().unwrap_or("");
+
+        println!(
+            "{},{},{},{},{}",
+            csv_field(&record.file), lat, lng, csv_field(address), csv_field(error)
+        );
+    }
+
+    Ok(())
+}
+
+///
+/// CSVフィールドのエスケープ
+///
+/// # 引数
+/// * `value` - エスケープ対象の文字列
+///
+/// # 戻り値
+/// カンマ・ダブルクォート・改行を含む場合はダブルクォートで囲み、内部のダブ
+/// ルクォートを二重化した文字列を返す。それ以外の場合はそのまま返す。
+///
+fn csv_field(value: &str) -> String {
+    if value.contains(',') || value.contains('"') || value.contains('\n') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+///
+/// GeoJSON形式での出力
+///
+fn emit_geojson(records: &[Record]) -> Result<()> {
+    let features = records.iter().map(to_feature).collect();
+    let collection = FeatureCollection { kind: "FeatureCollection", features };
+
+    println!("{}", serde_json::to_string_pretty(&collection)?);
+
+    Ok(())
+}
+
+///
+/// レコードのGeoJSON`Feature`への変換
+///
+fn to_feature(record: &Record) -> Feature {
+    let geometry = record.position.map(|pos| Geometry {
+        kind: "Point",
+        coordinates: [pos.lng(), pos.lat()],
+    });
+
+    Feature {
+        kind: "Feature",
+        geometry,
+        properties: Properties {
+            file: &record.file,
+            address: record.address.as_deref(),
+            error: record.error.as_deref(),
+        },
+    }
+}
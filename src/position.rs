@@ -0,0 +1,95 @@
+/*
+ * Reverse geocoder for Exif location data
+ *
+ *  Copyright (C) 2025 Hiroshi KUWAGATA <kgt9221@gmail.com>
+ */
+
+//!
+//! 緯度経度を表す位置情報型をまとめたモジュール
+//!
+
+use std::hash::{Hash, Hasher};
+
+///
+/// 緯度経度を表す構造体
+///
+/// # 注記
+/// `Eq`及び`Hash`は、生成時に指定された精度(小数点以下の桁数)で量子化した
+/// 値を用いて実装している。これは、浮動小数点数をそのままハッシュキーとして
+/// 使用できない事、及びほぼ同一の地点をキャッシュ上で同一視する為である。
+///
+#[derive(Debug, Clone, Copy)]
+pub struct Position {
+    /// 北緯
+    lat: f64,
+
+    /// 東経
+    lng: f64,
+
+    /// 量子化に用いる精度(小数点以下の桁数)
+    precision: u32,
+}
+
+impl Position {
+    ///
+    /// オブジェクトの生成
+    ///
+    /// # 引数
+    /// * `lat` - 北緯
+    /// * `lng` - 東経
+    /// * `precision` - 量子化に用いる精度(小数点以下の桁数)
+    ///
+    /// # 戻り値
+    /// 生成されたオブジェクトを返す。
+    ///
+    pub fn new(lat: f64, lng: f64, precision: u32) -> Self {
+        Self { lat, lng, precision }
+    }
+
+    ///
+    /// 北緯へのアクセサ
+    ///
+    pub fn lat(&self) -> f64 {
+        self.lat
+    }
+
+    ///
+    /// 東経へのアクセサ
+    ///
+    pub fn lng(&self) -> f64 {
+        self.lng
+    }
+
+    ///
+    /// 値の量子化
+    ///
+    /// # 引数
+    /// * `value` - 量子化対象の値
+    ///
+    /// # 戻り値
+    /// `precision`で指定された桁数でスケーリングし、丸めた整数値を返す。
+    ///
+    fn quantize(&self, value: f64) -> i64 {
+        let scale = 10f64.powi(self.precision as i32);
+        (value * scale).round() as i64
+    }
+}
+
+// PartialEqトレイトの実装
+impl PartialEq for Position {
+    fn eq(&self, other: &Self) -> bool {
+        self.quantize(self.lat) == other.quantize(other.lat)
+            && self.quantize(self.lng) == other.quantize(other.lng)
+    }
+}
+
+// Eqトレイトの実装
+impl Eq for Position {}
+
+// Hashトレイトの実装
+impl Hash for Position {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.quantize(self.lat).hash(state);
+        self.quantize(self.lng).hash(state);
+    }
+}
@@ -0,0 +1,72 @@
+/*
+ * Reverse geocoder for Exif location data
+ *
+ *  Copyright (C) 2025 Hiroshi KUWAGATA <kgt9221@gmail.com>
+ */
+
+//!
+//! 逆ジオコーディングAPIへの問い合わせ頻度を制限する処理をまとめたモジュール
+//!
+
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+///
+/// トークンバケット方式のレート制限器
+///
+/// # 注記
+/// 複数スレッドから共有され、`acquire()`の呼び出し毎に設定された秒間リクエス
+/// ト数を超えないよう必要に応じてスレッドをブロックする。
+///
+#[derive(Debug)]
+pub struct RateLimiter {
+    /// リクエスト間の最小間隔(`None`の場合は無制限)
+    interval: Option<Duration>,
+
+    /// 次にリクエストを許可できる時刻
+    next: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    ///
+    /// オブジェクトの生成
+    ///
+    /// # 引数
+    /// * `rate` - 許容する秒間リクエスト数。`0.0`以下を指定した場合は無制限
+    ///
+    /// # 戻り値
+    /// 生成されたオブジェクトを返す。
+    ///
+    pub fn new(rate: f64) -> Self {
+        let interval = if rate > 0.0 {
+            Some(Duration::from_secs_f64(1.0 / rate))
+        } else {
+            None
+        };
+
+        Self { interval, next: Mutex::new(Instant::now()) }
+    }
+
+    ///
+    /// リクエスト許可の取得
+    ///
+    /// # 注記
+    /// 設定された間隔に達していない場合、呼び出し元スレッドをブロックする。
+    ///
+    pub fn acquire(&self) {
+        let interval = match self.interval {
+            Some(interval) => interval,
+            None => return,
+        };
+
+        let mut next = self.next.lock().unwrap();
+        let now = Instant::now();
+
+        if *next > now {
+            thread::sleep(*next - now);
+        }
+
+        *next = std::cmp::max(*next, now) + interval;
+    }
+}
@@ -5,22 +5,23 @@
  */
 
 //!
-//! 国土地理院逆ジオコーディング APIの呼び出しによる緯度経度→住所変換処理をま
-//! とめたモジュール
+//! 国土地理院逆ジオコーディングAPIを用いた`GeocodingProvider`の実装をまとめた
+//! モジュール
 //!
 
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::path::Path;
 
 use anyhow::Result;
 use serde::Deserialize;
 
-use crate::cmd_args::Options;
 use crate::municd::{self, MuniCdRecord};
 
 #[allow(unused_imports)]
 use log::{debug, error, info, trace, warn};
 
+use super::GeocodingProvider;
+
 /// 国土地理院逆ジオコーディングAPIのベースURL
 const REVERSE_GEOCODE_URL: &str =
       "https://mreversegeocoder.gsi.go.jp/reverse-geocoder/LonLatToAddress";
@@ -76,42 +77,36 @@ struct GeometoryInfo {
 }
 
 ///
-/// 逆ジオコーディングインタフェース構造体
+/// 国土地理院逆ジオコーディングAPIを利用するプロバイダ
+///
+/// # 注記
+/// 日本国内の座標のみを対象とする。市町村コードから都道府県名・市町村名を補
+/// 完する為、MuniCdデータベースを保持する。
 ///
 #[derive(Debug)]
-pub(crate) struct ReverseGeocoder {
+pub(crate) struct GsiProvider {
     /// 市町村コードをキーとした市町村データベース
     municd: HashMap<String, MuniCdRecord>,
 }
 
-impl ReverseGeocoder {
+impl GsiProvider {
     ///
-    /// 逆ジオコーディングインターフェースオブジェクトの生成
+    /// オブジェクトの生成
     ///
     /// # 引数
-    /// * `opts` - オプション情報をパックしたオブジェクト
+    /// * `cache_path` - MuniCdキャッシュファイルへのパス
     ///
     /// # 戻り値
     /// 処理に成功した場合は、生成したオブジェクトを`Ok()`でラップして返す。
     ///
-    pub(crate) fn new(opts: Arc<Options>) -> Result<Self> {
-        Ok(Self { municd: municd::load(opts.municd_cache())?})
+    pub(crate) fn new(cache_path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self { municd: municd::load(cache_path)? })
     }
+}
 
-    ///
-    /// 住所の照会
-    ///
-    /// # 引数
-    /// * `lat` - 照会する北緯 
-    /// * `lng` - 照会する東経
-    ///
-    /// # 戻り値
-    /// 照会に成功した場合は、照会できた住所を`Ok()`でラップして返す。
-    ///
-    /// # 注記
-    /// 本メソッドは、国土地理院の逆ジオコーディングAPIの呼び出しを行う。
-    ///
-    pub(crate) fn query(&self, lat: f64, lng: f64) -> Result<String> {
+// GeocodingProviderトレイトの実装
+impl GeocodingProvider for GsiProvider {
+    fn reverse(&self, lat: f64, lng: f64) -> Result<String> {
         let url = format!("{}?lat={}&lon={}", REVERSE_GEOCODE_URL, lat, lng);
 
         info!("query to {}", url);
@@ -0,0 +1,292 @@
+/*
+ * Reverse geocoder for Exif location data
+ *
+ *  Copyright (C) 2025 Hiroshi KUWAGATA <kgt9221@gmail.com>
+ */
+
+//!
+//! 緯度経度→住所変換(逆ジオコーディング)処理をまとめたモジュール
+//!
+
+mod gsi;
+mod nominatim;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Condvar, Mutex};
+
+use anyhow::{anyhow, Result};
+
+use crate::position::Position;
+use crate::rate_limiter::RateLimiter;
+
+use gsi::GsiProvider;
+use nominatim::NominatimProvider;
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+///
+/// 逆ジオコーディングAPIのプロバイダを指し示す列挙子
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provider {
+    /// 国土地理院の逆ジオコーディングAPI(日本国内限定)
+    Gsi,
+
+    /// Nominatim(OpenStreetMap)の逆ジオコーディングAPI(世界対応)
+    Nominatim,
+}
+
+///
+/// `ReverseGeocoder`の生成に必要な設定
+///
+#[derive(Debug, Clone)]
+pub struct GeocoderConfig {
+    /// 使用する逆ジオコーディングAPIのプロバイダ
+    pub provider: Provider,
+
+    /// 市町村コードのキャッシュファイルへのパス(`Provider::Gsi`選択時のみ
+    /// 使用される)
+    pub municd_cache: PathBuf,
+}
+
+///
+/// 逆ジオコーディングAPIのプロバイダが実装するトレイト
+///
+/// # 注記
+/// プロバイダ毎のAPI仕様(エンドポイント、レスポンス形式)の違いは本トレイト
+/// の実装側に閉じ込め、`ReverseGeocoder`からは意識しない。
+///
+pub(crate) trait GeocodingProvider: std::fmt::Debug + Send + Sync {
+    ///
+    /// 住所の照会
+    ///
+    /// # 引数
+    /// * `lat` - 照会する北緯
+    /// * `lng` - 照会する東経
+    ///
+    /// # 戻り値
+    /// 照会に成功した場合は、照会できた住所を`Ok()`でラップして返す。
+    ///
+    fn reverse(&self, lat: f64, lng: f64) -> Result<String>;
+}
+
+///
+/// メモ化キャッシュの1エントリ
+///
+/// # 注記
+/// 照会が完了するまでの間、同一の位置情報に対する後続の照会が重複してプロバ
+/// イダへ問い合わせを行わないよう、進行中の照会を`InFlight`として記録する。
+///
+#[derive(Debug)]
+enum MemoEntry {
+    /// 照会が完了し、結果が確定している
+    Done(String),
+
+    /// 他スレッドが既に照会を開始しており、その完了を待つべき状態
+    InFlight(Arc<InFlight>),
+}
+
+///
+/// 進行中の照会を他スレッドと同期する為の構造体
+///
+#[derive(Debug, Default)]
+struct InFlight {
+    /// 照会結果(未完了の間は`None`)
+    result: Mutex<Option<Result<String, String>>>,
+
+    /// 結果確定時に待機スレッドを起床させる為の条件変数
+    cond: Condvar,
+}
+
+impl InFlight {
+    ///
+    /// 照会の完了を待ち受ける
+    ///
+    /// # 戻り値
+    /// 照会を行ったスレッドが確定させた結果を返す。
+    ///
+    fn wait(&self) -> Result<String, String> {
+        let mut result = self.result.lock().unwrap();
+
+        while result.is_none() {
+            result = self.cond.wait(result).unwrap();
+        }
+
+        result.clone().unwrap()
+    }
+
+    ///
+    /// 照会結果の確定
+    ///
+    /// # 引数
+    /// * `result` - 確定させる照会結果
+    ///
+    /// # 戻り値
+    /// 本関数の呼び出し後、待機中の全スレッドが`wait()`から復帰する。
+    ///
+    fn finish(&self, result: Result<String, String>) {
+        *self.result.lock().unwrap() = Some(result);
+        self.cond.notify_all();
+    }
+}
+
+///
+/// 逆ジオコーディングインタフェース構造体
+///
+#[derive(Debug)]
+pub struct ReverseGeocoder {
+    /// 使用する逆ジオコーディングAPIのプロバイダ
+    provider: Box<dyn GeocodingProvider>,
+
+    /// 位置情報をキーとした照会結果のメモ化キャッシュ
+    ///
+    /// # 注記
+    /// 並行実行されるワーカースレッドから共有される為、`Mutex`で保護する。
+    memo: Mutex<HashMap<Position, MemoEntry>>,
+}
+
+impl ReverseGeocoder {
+    ///
+    /// 逆ジオコーディングインターフェースオブジェクトの生成
+    ///
+    /// # 引数
+    /// * `config` - `ReverseGeocoder`の生成に必要な設定
+    ///
+    /// # 戻り値
+    /// 処理に成功した場合は、生成したオブジェクトを`Ok()`でラップして返す。
+    ///
+    pub fn new(config: GeocoderConfig) -> Result<Self> {
+        let provider: Box<dyn GeocodingProvider> = match config.provider {
+            Provider::Gsi => Box::new(GsiProvider::new(config.municd_cache)?),
+            Provider::Nominatim => Box::new(NominatimProvider::new()),
+        };
+
+        Ok(Self { provider, memo: Mutex::new(HashMap::new()) })
+    }
+
+    ///
+    /// 住所の照会
+    ///
+    /// # 引数
+    /// * `pos` - 照会する位置情報
+    /// * `limiter` - プロバイダへの問い合わせ頻度を制限するレート制限器
+    ///
+    /// # 戻り値
+    /// 照会に成功した場合は、照会できた住所を`Ok()`でラップして返す。
+    ///
+    /// # 注記
+    /// `pos`が量子化した上で既に照会済みの位置情報と一致する場合は、メモ化
+    /// キャッシュから結果を返し、プロバイダの呼び出し(及びレート制限)は行
+    /// わない。また、同一の位置情報に対する照会が既に他スレッドで進行中の場
+    /// 合は、自身がプロバイダへ問い合わせる事はせず、その完了を待って結果を
+    /// 共有する(single-flight)。
+    ///
+    pub fn query(&self, pos: Position, limiter: &RateLimiter) -> Result<String> {
+        let in_flight = {
+            let mut memo = self.memo.lock().unwrap();
+
+            match memo.get(&pos) {
+                Some(MemoEntry::Done(address)) => {
+                    debug!("memo hit for ({}, {})", pos.lat(), pos.lng());
+                    return Ok(address.clone());
+                }
+
+                Some(MemoEntry::InFlight(in_flight)) => in_flight.clone(),
+
+                None => {
+                    let in_flight = Arc::new(InFlight::default());
+                    memo.insert(pos, MemoEntry::InFlight(in_flight.clone()));
+                    drop(memo);
+
+                    return self.resolve(pos, limiter, &in_flight);
+                }
+            }
+        };
+
+        debug!("join in-flight query for ({}, {})", pos.lat(), pos.lng());
+        in_flight.wait().map_err(|err| anyhow!(err))
+    }
+
+    ///
+    /// プロバイダへの問い合わせと、結果のメモ化・共有
+    ///
+    /// # 引数
+    /// * `pos` - 照会する位置情報
+    /// * `limiter` - プロバイダへの問い合わせ頻度を制限するレート制限器
+    /// * `in_flight` - この照会を待ち受けている他スレッドとの同期用オブジェクト
+    ///
+    /// # 戻り値
+    /// 照会に成功した場合は、照会できた住所を`Ok()`でラップして返す。
+    ///
+    /// # 注記
+    /// 照会の成否に関わらず、結果を`in_flight`へ設定して待機中の全スレッドを
+    /// 起床させる。照会に失敗した場合はメモ化キャッシュからエントリを取り除
+    /// き、以降の照会で再試行できるようにする。`self.provider.reverse()`が
+    /// パニックした場合でも`ResolveGuard`がアンワインド時にこれらの後始末を
+    /// 行う為、待機中のスレッドが`InFlight::wait()`で永久に止まる事はない。
+    ///
+    fn resolve(
+        &self, pos: Position, limiter: &RateLimiter, in_flight: &Arc<InFlight>
+    ) -> Result<String> {
+        let mut guard = ResolveGuard::new(self, pos, in_flight.clone());
+
+        limiter.acquire();
+        let result = self.provider.reverse(pos.lat(), pos.lng());
+
+        guard.disarm();
+
+        in_flight.finish(result.as_ref().cloned().map_err(|err| err.to_string()));
+
+        let mut memo = self.memo.lock().unwrap();
+        match &result {
+            Ok(address) => { memo.insert(pos, MemoEntry::Done(address.clone())); }
+            Err(_) => { memo.remove(&pos); }
+        }
+
+        result
+    }
+}
+
+///
+/// `resolve()`内でのパニックから`InFlight`の待機者及びメモ化キャッシュを保
+/// 護するRAIIガード
+///
+/// # 注記
+/// `self.provider.reverse()`の呼び出し中にパニックが発生すると、通常の制御
+/// フローによる`InFlight::finish()`呼び出し及びメモ化キャッシュの後始末が行
+/// われない。本ガードがスコープを抜ける際(アンワインドを含む)に`armed`が真
+/// のままであれば、待機中のスレッドを失敗として起床させ、`InFlight`エントリ
+/// をメモ化キャッシュから取り除く。正常系では`disarm()`を呼び、以降の後始末
+/// は`resolve()`自身に委ねる。
+///
+struct ResolveGuard<'a> {
+    geocoder: &'a ReverseGeocoder,
+    pos: Position,
+    in_flight: Arc<InFlight>,
+    armed: bool,
+}
+
+impl<'a> ResolveGuard<'a> {
+    fn new(geocoder: &'a ReverseGeocoder, pos: Position, in_flight: Arc<InFlight>) -> Self {
+        Self { geocoder, pos, in_flight, armed: true }
+    }
+
+    fn disarm(&mut self) {
+        self.armed = false;
+    }
+}
+
+impl Drop for ResolveGuard<'_> {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
+
+        error!("reverse geocoding query aborted unexpectedly, releasing waiters");
+
+        self.in_flight.finish(Err("reverse geocoding query aborted unexpectedly".to_string()));
+        self.geocoder.memo.lock().unwrap().remove(&self.pos);
+    }
+}
@@ -0,0 +1,79 @@
+/*
+ * Reverse geocoder for Exif location data
+ *
+ *  Copyright (C) 2025 Hiroshi KUWAGATA <kgt9221@gmail.com>
+ */
+
+//!
+//! Nominatim(OpenStreetMap)逆ジオコーディングAPIを用いた`GeocodingProvider`の
+//! 実装をまとめたモジュール
+//!
+
+use anyhow::Result;
+use serde::Deserialize;
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+use super::GeocodingProvider;
+
+/// Nominatim逆ジオコーディングAPIのベースURL
+const REVERSE_GEOCODE_URL: &str =
+    "https://nominatim.openstreetmap.org/reverse";
+
+/// APIへ送出するUser-Agent(Nominatimの利用ポリシー上必須)
+const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION")
+);
+
+///
+/// APIからのレスポンスを格納する構造体
+///
+#[derive(Debug, Deserialize)]
+struct ApiResult {
+    /// 整形済みの住所文字列
+    display_name: String,
+}
+
+///
+/// Nominatim逆ジオコーディングAPIを利用するプロバイダ
+///
+/// # 注記
+/// 世界中の座標を対象とする事ができる。MuniCdデータベースのような市町村コー
+/// ド補完は行わず、APIが返す`display_name`をそのまま住所として用いる。
+///
+#[derive(Debug)]
+pub(crate) struct NominatimProvider;
+
+impl NominatimProvider {
+    ///
+    /// オブジェクトの生成
+    ///
+    /// # 戻り値
+    /// 生成したオブジェクトを返す。
+    ///
+    pub(crate) fn new() -> Self {
+        Self
+    }
+}
+
+// GeocodingProviderトレイトの実装
+impl GeocodingProvider for NominatimProvider {
+    fn reverse(&self, lat: f64, lng: f64) -> Result<String> {
+        let url = format!(
+            "{}?format=json&lat={}&lon={}", REVERSE_GEOCODE_URL, lat, lng
+        );
+
+        info!("query to {}", url);
+        let client = reqwest::blocking::Client::new();
+        let result = client
+            .get(&url)
+            .header(reqwest::header::USER_AGENT, USER_AGENT)
+            .send()?
+            .json::<ApiResult>()?;
+
+        debug!("{:?}", result);
+
+        Ok(result.display_name)
+    }
+}
@@ -0,0 +1,268 @@
+/*
+ * Reverse geocoder for Exif location data
+ *
+ *  Copyright (C) 2025 Hiroshi KUWAGATA <kgt9221@gmail.com>
+ */
+
+//!
+//! ファイル毎の処理をワーカースレッドのプールで並行実行する処理をまとめたモ
+//! ジュール
+//!
+
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+use anyhow::{anyhow, Result};
+use exifgeo::gps_info;
+use exifgeo::position::Position;
+use exifgeo::rate_limiter::RateLimiter;
+use exifgeo::reverse_geocoder::ReverseGeocoder;
+
+use crate::cmd_args::{Options, OutputFormat};
+use crate::output::{self, Record};
+
+#[allow(unused_imports)]
+use log::{debug, error, info, trace, warn};
+
+///
+/// 1ファイル分の処理結果
+///
+enum Outcome {
+    /// 住所の照会に成功
+    Succeed(String, Position),
+
+    /// 位置情報の問い合わせに失敗
+    QueryFailed(anyhow::Error, Position),
+
+    /// ファイルに位置情報が含まれていなかった
+    NoGpsInfo,
+
+    /// 位置情報の読み出しに失敗
+    ReadFailed(anyhow::Error),
+}
+
+///
+/// ワーカースレッドのプールによるファイル毎の処理の並行実行
+///
+/// # 引数
+/// * `opts` - オプション情報をパックしたオブジェクト
+/// * `coder` - 逆ジオコーディングインタフェースオブジェクト
+///
+/// # 戻り値
+/// 処理に成功した場合は`Ok(())`を返す。
+///
+/// # 注記
+/// `opts.jobs()`で指定された数のワーカースレッドが共有のファイルキューから
+/// ファイルを取り出して処理する。`opts.rate()`で指定された頻度を超えないよ
+/// うレート制限器を全ワーカーで共有し、結果は入力順に整列した上で出力する。
+///
+pub(crate) fn run(opts: Arc<Options>, coder: Arc<ReverseGeocoder>) -> Result<()> {
+    let files = opts.target_files().clone();
+    let total = files.len();
+
+    let next_index = Arc::new(AtomicUsize::new(0));
+    let processed = Arc::new(AtomicUsize::new(0));
+    let limiter = Arc::new(RateLimiter::new(opts.rate()));
+    let (tx, rx) = mpsc::channel();
+
+    let handles: Vec<_> = (0..opts.jobs())
+        .map(|_| {
+            let files = files.clone();
+            let next_index = next_index.clone();
+            let processed = processed.clone();
+            let limiter = limiter.clone();
+            let coder = coder.clone();
+            let opts = opts.clone();
+            let tx = tx.clone();
+
+            thread::spawn(move || {
+                loop {
+                    let idx = next_index.fetch_add(1, Ordering::SeqCst);
+
+                    if idx >= files.len() {
+                        break;
+                    }
+
+                    let file = files[idx].clone();
+
+                    info!("try {}", file.display());
+
+                    // ワーカー内でのパニックが該当インデックスの未送出(ひ
+                    // いては以降の結果が`pending`に滞留し出力から消える)に
+                    // つながらないよう、1ファイル分の処理はここで捕捉する。
+                    let outcome = panic::catch_unwind(
+                        AssertUnwindSafe(|| process(&file, &opts, &coder, &limiter))
+                    ).unwrap_or_else(|payload| {
+                        Outcome::ReadFailed(anyhow!(
+                            "worker panicked: {}", panic_message(&payload)
+                        ))
+                    });
+
+                    let done = processed.fetch_add(1, Ordering::SeqCst) + 1;
+                    eprintln!("progress: {}/{}", done, total);
+
+                    if tx.send((idx, file, outcome)).is_err() {
+                        break;
+                    }
+                }
+            })
+        })
+        .collect();
+
+    drop(tx);
+
+    /*
+     * 受信した結果を入力順に並び替えて出力(もしくは記録)
+     */
+    let format = opts.output();
+    let mut pending = HashMap::new();
+    let mut records = Vec::with_capacity(total);
+    let mut next_report = 0usize;
+
+    for (idx, file, outcome) in rx {
+        pending.insert(idx, (file, outcome));
+
+        while let Some((file, outcome)) = pending.remove(&next_report) {
+            let record = to_record(&file, outcome);
+
+            if format == OutputFormat::Text {
+                report(&record);
+            }
+
+            records.push(record);
+            next_report += 1;
+        }
+    }
+
+    for handle in handles {
+        if handle.join().is_err() {
+            return Err(anyhow!("worker thread panicked unexpectedly"));
+        }
+    }
+
+    /*
+     * テキスト形式以外は、全件の処理完了後にまとめて出力
+     */
+    output::emit(&records, format)?;
+
+    Ok(())
+}
+
+///
+/// パニックのペイロードからメッセージ文字列を取り出す
+///
+/// # 引数
+/// * `payload` - `catch_unwind`が捕捉したパニックのペイロード
+///
+/// # 戻り値
+/// ペイロードが`&str`または`String`であればその内容を、それ以外であれば
+/// 汎用のメッセージを返す。
+///
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+///
+/// 1ファイル分の処理
+///
+/// # 引数
+/// * `file` - 処理対象のファイルのパス
+/// * `opts` - オプション情報をパックしたオブジェクト
+/// * `coder` - 逆ジオコーディングインタフェースオブジェクト
+/// * `limiter` - レート制限器
+///
+/// # 戻り値
+/// 処理結果
+///
+fn process(
+    file: &PathBuf,
+    opts: &Options,
+    coder: &ReverseGeocoder,
+    limiter: &RateLimiter,
+) -> Outcome {
+    let pos = match gps_info::read(file, opts.precision()) {
+        Ok(Some(pos)) => pos,
+        Ok(None) => return Outcome::NoGpsInfo,
+        Err(err) => return Outcome::ReadFailed(err),
+    };
+
+    match coder.query(pos, limiter) {
+        Ok(address) => Outcome::Succeed(address, pos),
+        Err(err) => Outcome::QueryFailed(err, pos),
+    }
+}
+
+///
+/// 処理結果の`Record`への変換
+///
+/// # 引数
+/// * `file` - 処理対象のファイルのパス
+/// * `outcome` - 処理結果
+///
+/// # 戻り値
+/// 出力形式に依存しない形に整形された処理結果
+///
+fn to_record(file: &PathBuf, outcome: Outcome) -> Record {
+    let file = file.display().to_string();
+
+    match outcome {
+        Outcome::Succeed(address, pos) => {
+            Record { file, position: Some(pos), address: Some(address), error: None }
+        }
+
+        Outcome::QueryFailed(err, pos) => {
+            Record { file, position: Some(pos), address: None, error: Some(err.to_string()) }
+        }
+
+        Outcome::NoGpsInfo => {
+            Record { file, position: None, address: None, error: None }
+        }
+
+        Outcome::ReadFailed(err) => {
+            Record { file, position: None, address: None, error: Some(err.to_string()) }
+        }
+    }
+}
+
+///
+/// 処理結果の表示(テキスト形式)
+///
+/// # 引数
+/// * `record` - 処理結果
+///
+fn report(record: &Record) {
+    match (&record.position, &record.address, &record.error) {
+        (Some(pos), Some(address), _) => {
+            println!(
+                "{}\n\t{} ({:.2}\u{00b0},{:.2}\u{00b0})",
+                record.file, address, pos.lat(), pos.lng()
+            );
+        }
+
+        (Some(pos), None, Some(err)) => {
+            eprintln!(
+                "{}: 位置情報問い合わせ失敗({:.2}\u{00b0},{:.2}\u{00b0}, {})",
+                record.file, pos.lat(), pos.lng(), err
+            );
+        }
+
+        (None, _, Some(err)) => {
+            eprintln!("{}: 位置情報読み出し失敗({})", record.file, err);
+        }
+
+        (None, _, None) => {
+            eprintln!("{}: 位置情報無し", record.file);
+        }
+    }
+}